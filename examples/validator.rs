@@ -1,8 +1,13 @@
 // #![feature(trait_alias)]
 use async_std::io;
 use async_std::task;
+use cookie::Cookie;
 use futures::future::BoxFuture;
+use multimap::MultiMap;
+use serde::de::value::{Error as ValueError, StrDeserializer};
+use serde::de::{DeserializeOwned, IntoDeserializer};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use tide::{Middleware, Next, Request, Response};
 
 use std::collections::HashMap;
@@ -61,22 +66,87 @@ struct CustomError {
     message: String,
 }
 
+/// Stands in for a database/cache lookup a real async validator would make.
+async fn is_city_known(city: &str) -> bool {
+    matches!(city, "Paris" | "London" | "Berlin")
+}
+
 fn main() -> io::Result<()> {
     task::block_on(async {
         let mut app = tide::new();
 
         let mut validator_middleware = ValidatorMiddleware::new();
-        validator_middleware.add_validator(ParameterType::Param("n"), is_number);
+        validator_middleware.validate_as::<i64, _, _>(
+            ParameterType::Param("n"),
+            |param_type, value, expected_type| CustomError {
+                status_code: 400,
+                message: format!(
+                    "'{}' ({}) is not a valid {}",
+                    value,
+                    param_type.name(),
+                    expected_type
+                ),
+            },
+            |n| {
+                if *n > 0 {
+                    Ok(())
+                } else {
+                    Err(CustomError {
+                        status_code: 400,
+                        message: format!("'{}' must be strictly positive", n),
+                    })
+                }
+            },
+        );
         validator_middleware.add_validator(ParameterType::Header("X-Custom-Header"), is_number);
         validator_middleware.add_validator(ParameterType::QueryParam("test"), is_bool);
         validator_middleware.add_validator(ParameterType::QueryParam("test"), is_length_under(10));
         validator_middleware.add_validator(ParameterType::Cookie("test"), is_length_under(20));
+        validator_middleware
+            .add_validator(ParameterType::BodyField("address.city"), is_length_under(2));
+        validator_middleware
+            .add_validator_each(ParameterType::QueryParam("tag"), is_length_under(20));
+        validator_middleware.add_validator_count(ParameterType::QueryParam("tag"), 1, 5, |count| {
+            CustomError {
+                status_code: 400,
+                message: format!("expected between 1 and 5 'tag' values, got {}", count),
+            }
+        });
+        validator_middleware.add_required(ParameterType::Header("X-Custom-Header"), |param_type| {
+            CustomError {
+                status_code: 400,
+                message: format!("missing required {}", param_type.name()),
+            }
+        });
+        // Trim and lowercase `test` so handlers never see a raw, differently-cased value.
+        validator_middleware
+            .add_validator_ctx(ParameterType::QueryParam("test"), |value, _info| {
+                Ok(Sanitized::Replace(value.trim().to_lowercase()))
+            });
+        validator_middleware.add_async_validator(
+            ParameterType::BodyField("address.city"),
+            |city| async move {
+                if is_city_known(&city).await {
+                    Ok(())
+                } else {
+                    Err(CustomError {
+                        status_code: 422,
+                        message: format!("'{}' is not a city we deliver to", city),
+                    })
+                }
+            },
+        );
 
         app.at("/test/:n").middleware(validator_middleware).get(
-            |_: tide::Request<()>| async move {
+            |req: tide::Request<()>| async move {
                 let cat = Cat {
                     name: "chashu".into(),
                 };
+                if let Some(params) = req.ext::<ValidatedParams>() {
+                    if let Some(test) = params.get(&ParameterType::QueryParam("test")) {
+                        println!("sanitized 'test' query param: {}", test);
+                    }
+                }
                 tide::Response::new(200).body_json(&cat).unwrap()
             },
         );
@@ -85,23 +155,241 @@ fn main() -> io::Result<()> {
         Ok(())
     })
 }
-// TODO: add validation about cookies, headers and maybe body ? https://express-validator.github.io/docs/check-api.html
 // TODO: add ctx in closure to have other informations about request ? Maybe in further version
-// TODO: add required param
 // trait Validator = Fn(&str) -> Result<(), String> + Send + Sync + 'static;
 
-// #[derive(Debug, Clone, Hash, Eq, PartialEq)]
-// enum Field<'a> {
-//     Required(&'a str),
-//     Optional(&'a str),
-// }
-
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
 enum ParameterType<'a> {
     Param(&'a str),
     QueryParam(&'a str),
     Header(&'a str),
     Cookie(&'a str),
+    /// A dotted path into a JSON body, or a field name in a form body
+    /// (e.g. `BodyField("address.city")`).
+    BodyField(&'a str),
+    /// Validates the whole request body at once: a JSON body as re-serialized
+    /// JSON text, a form body as the `Debug` text of its field map, and a
+    /// body sent as JSON that doesn't parse as its raw text.
+    Body,
+}
+
+impl<'a> ParameterType<'a> {
+    /// A short, serializable label describing the kind of parameter,
+    /// independent of its name (used when reporting aggregated errors).
+    fn kind(&self) -> &'static str {
+        match self {
+            ParameterType::Param(_) => "param",
+            ParameterType::QueryParam(_) => "query",
+            ParameterType::Header(_) => "header",
+            ParameterType::Cookie(_) => "cookie",
+            ParameterType::BodyField(_) => "body_field",
+            ParameterType::Body => "body",
+        }
+    }
+
+    fn name(&self) -> &'a str {
+        match self {
+            ParameterType::Param(name)
+            | ParameterType::QueryParam(name)
+            | ParameterType::Header(name)
+            | ParameterType::Cookie(name)
+            | ParameterType::BodyField(name) => name,
+            ParameterType::Body => "",
+        }
+    }
+}
+
+/// The request body, parsed once and cached for the lifetime of `handle`.
+enum BodyData {
+    Json(Value),
+    /// `application/x-www-form-urlencoded` or `multipart/form-data`,
+    /// flattened to field name -> values (mirrors a multimap, since a form
+    /// field can repeat).
+    Form(HashMap<String, Vec<String>>),
+    /// A body was sent with a JSON `Content-Type` but didn't parse as JSON.
+    /// Carries the raw (lossily-decoded) text so a whole-body
+    /// `ParameterType::Body` validator still sees the actual malformed
+    /// payload and can reject it, instead of it silently looking absent or
+    /// like a real `null` body. There's no field to locate inside text that
+    /// isn't valid JSON, so `ParameterType::BodyField` treats this the same
+    /// as "field not found" (like `Form`/`Json` do when the path is
+    /// missing) rather than handing every field validator the whole blob.
+    Invalid(String),
+    /// No body was sent, or its `Content-Type` wasn't one we parse. Distinct
+    /// from `Json(Value::Null)`, which means a JSON body *was* sent and it
+    /// parsed to the literal `null` — only `Absent` should count as "missing"
+    /// for `ParameterType::Body` presence checks.
+    Absent,
+}
+
+/// Walks a dotted path (`"address.city"`) into a JSON value, returning the
+/// value found at the end of the path, if any.
+fn lookup_json_path<'v>(value: &'v Value, path: &str) -> Option<&'v Value> {
+    path.split('.')
+        .try_fold(value, |current, segment| current.get(segment))
+}
+
+/// Turns a JSON scalar into the `&str` validators expect; objects and arrays
+/// have no sensible string form and are treated as "not found".
+fn stringify_json_scalar(value: &Value) -> Option<String> {
+    match value {
+        Value::String(s) => Some(s.clone()),
+        Value::Number(n) => Some(n.to_string()),
+        Value::Bool(b) => Some(b.to_string()),
+        _ => None,
+    }
+}
+
+fn parse_urlencoded_form(body: &[u8]) -> HashMap<String, Vec<String>> {
+    let mut form = HashMap::new();
+    for (key, value) in url::form_urlencoded::parse(body) {
+        form.entry(key.into_owned())
+            .or_insert_with(Vec::new)
+            .push(value.into_owned());
+    }
+    form
+}
+
+/// A minimal multipart reader good enough for plain text fields: it doesn't
+/// try to handle file parts, just `name="..."` fields, mirroring salvo's
+/// multimap-based `FormData`.
+fn parse_multipart_form(body: &[u8], boundary: &str) -> HashMap<String, Vec<String>> {
+    let mut form = HashMap::new();
+    let delimiter = format!("--{}", boundary);
+    let body = String::from_utf8_lossy(body);
+
+    for part in body.split(&delimiter) {
+        let part = part.trim_matches(|c| c == '\r' || c == '\n');
+        if part.is_empty() || part == "--" {
+            continue;
+        }
+        let mut sections = part.splitn(2, "\r\n\r\n");
+        let headers = sections.next().unwrap_or("");
+        let content = sections.next().unwrap_or("").trim_end_matches("\r\n");
+
+        let name = headers
+            .split(';')
+            .map(|segment| segment.trim())
+            .find_map(|segment| segment.strip_prefix("name=\""))
+            .and_then(|rest| rest.strip_suffix('"'));
+
+        if let Some(name) = name {
+            form.entry(name.to_string())
+                .or_insert_with(Vec::new)
+                .push(content.to_string());
+        }
+    }
+    form
+}
+
+/// Parses a raw query string into a multimap so repeated keys (`?tag=a&tag=b`)
+/// keep every value instead of the last one winning.
+fn parse_query_multimap(query: &str) -> MultiMap<String, String> {
+    let mut map = MultiMap::new();
+    for (key, value) in url::form_urlencoded::parse(query.as_bytes()) {
+        map.insert(key.into_owned(), value.into_owned());
+    }
+    map
+}
+
+/// Parses a raw `Cookie` header (`a=1; b=2; a=3`) into a multimap, since a
+/// cookie jar that keeps only the last value of a repeated name can't answer
+/// "how many were sent". Still splits pairs on `;` ourselves, but hands each
+/// `name=value` pair to the `cookie` crate's own `parse_encoded`, so percent-
+/// encoded and DQUOTE-wrapped values come out the same way `ctx.cookie()`
+/// used to decode them, rather than as the raw header text.
+fn parse_cookie_multimap(cookie_header: &str) -> MultiMap<String, String> {
+    let mut map = MultiMap::new();
+    for pair in cookie_header.split(';') {
+        let pair = pair.trim();
+        if pair.is_empty() {
+            continue;
+        }
+        if let Ok(cookie) = Cookie::parse_encoded(pair.to_string()) {
+            map.insert(cookie.name().to_string(), cookie.value().to_string());
+        }
+    }
+    map
+}
+
+/// All values of a (possibly repeated) header, owned so they outlive the
+/// borrow of `ctx`.
+fn header_all_values<State>(ctx: &Request<State>, name: &str) -> Vec<String> {
+    ctx.headers()
+        .get_all(name)
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .map(|value| value.to_string())
+        .collect()
+}
+
+fn extract_boundary(content_type: &str) -> Option<&str> {
+    content_type
+        .split(';')
+        .map(|segment| segment.trim())
+        .find_map(|segment| segment.strip_prefix("boundary="))
+        .map(|boundary| boundary.trim_matches('"'))
+}
+
+/// What a validator that also inspects `RequestInfo` may do to the value it
+/// was handed before the rest of the pipeline sees it.
+#[derive(Debug, Clone)]
+enum Sanitized {
+    Unchanged,
+    Replace(String),
+}
+
+/// Everything about the request that a context-aware validator may want,
+/// besides the value it's checking.
+struct RequestInfo<'r> {
+    method: String,
+    path: String,
+    peer_addr: Option<String>,
+    query: &'r MultiMap<String, String>,
+}
+
+/// Sanitized values a `Sanitized::Replace`-returning validator produced,
+/// keyed by the `ParameterType` they replaced. A repeatable parameter
+/// (`QueryParam`/`Header`/`Cookie`) can sanitize more than one value, so
+/// entries accumulate in a `MultiMap` instead of the last value overwriting
+/// the rest. Stashed on the request via `ctx.set_ext` so handlers can pull
+/// normalized values out instead of the raw ones.
+#[derive(Debug, Clone, Default)]
+struct ValidatedParams(MultiMap<ParameterType<'static>, String>);
+
+impl ValidatedParams {
+    /// The first sanitized value stashed for `param_type`, if any.
+    pub fn get(&self, param_type: &ParameterType<'static>) -> Option<&str> {
+        self.0.get(param_type).map(String::as_str)
+    }
+
+    /// Every sanitized value stashed for `param_type`, in the order their
+    /// validators ran.
+    pub fn get_all(&self, param_type: &ParameterType<'static>) -> &[String] {
+        self.0.get_vec(param_type).map(Vec::as_slice).unwrap_or(&[])
+    }
+}
+
+/// A single failed validation, reported alongside enough context to locate
+/// and fix it: which kind of parameter it was, its name, the value that was
+/// rejected, and the validator's own error.
+#[derive(Debug, Serialize)]
+struct FieldError<T> {
+    kind: &'static str,
+    name: String,
+    value: String,
+    error: T,
+}
+
+impl<T> FieldError<T> {
+    fn new(param_type: &ParameterType<'_>, value: impl Into<String>, error: T) -> Self {
+        FieldError {
+            kind: param_type.kind(),
+            name: param_type.name().to_string(),
+            value: value.into(),
+            error,
+        }
+    }
 }
 
 struct ValidatorMiddleware<T>
@@ -110,8 +398,39 @@ where
 {
     validators: HashMap<
         ParameterType<'static>,
-        Vec<Arc<dyn Fn(&str) -> Result<(), T> + Send + Sync + 'static>>,
+        Vec<
+            Arc<
+                dyn for<'r> Fn(&str, &RequestInfo<'r>) -> Result<Sanitized, T>
+                    + Send
+                    + Sync
+                    + 'static,
+            >,
+        >,
     >,
+    /// Cardinality constraints registered through `add_validator_count`:
+    /// how many values a repeatable `QueryParam`/`Header`/`Cookie` must carry.
+    count_validators: HashMap<
+        ParameterType<'static>,
+        (
+            usize,
+            usize,
+            Arc<dyn Fn(usize) -> T + Send + Sync + 'static>,
+        ),
+    >,
+    /// Parameters registered through `add_required`: must be present before
+    /// their value validators even run.
+    required: HashMap<
+        ParameterType<'static>,
+        Arc<dyn Fn(&ParameterType<'static>) -> T + Send + Sync + 'static>,
+    >,
+    /// Validators registered through `add_async_validator`. These only run,
+    /// per value, once every sync validator for that same value has passed,
+    /// so a malformed value never triggers the expensive round trip.
+    async_validators: HashMap<
+        ParameterType<'static>,
+        Vec<Arc<dyn Fn(String) -> BoxFuture<'static, Result<(), T>> + Send + Sync + 'static>>,
+    >,
+    fail_fast: bool,
 }
 
 impl<T> ValidatorMiddleware<T>
@@ -121,9 +440,22 @@ where
     pub fn new() -> Self {
         ValidatorMiddleware {
             validators: HashMap::new(),
+            count_validators: HashMap::new(),
+            required: HashMap::new(),
+            async_validators: HashMap::new(),
+            fail_fast: false,
         }
     }
 
+    /// When `true`, `handle` returns a `400` on the first invalid field it
+    /// encounters, as it always used to. When `false` (the default), every
+    /// registered validator runs and a single `422` is returned carrying
+    /// every failure found.
+    pub fn fail_fast(mut self, fail_fast: bool) -> Self {
+        self.fail_fast = fail_fast;
+        self
+    }
+
     pub fn with_validators<F>(mut self, validators: HashMap<ParameterType<'static>, F>) -> Self
     where
         F: Fn(&str) -> Result<(), T> + Send + Sync + 'static,
@@ -137,6 +469,19 @@ where
     pub fn add_validator<F>(&mut self, param_name: ParameterType<'static>, validator: F)
     where
         F: Fn(&str) -> Result<(), T> + Send + Sync + 'static,
+    {
+        self.add_validator_ctx(param_name, move |value, _info| {
+            validator(value).map(|_| Sanitized::Unchanged)
+        });
+    }
+
+    /// Registers a context-aware validator: besides the raw value, it also
+    /// sees `RequestInfo` (method, path, peer address, query parameters) and
+    /// may return `Sanitized::Replace` to normalize the value for handlers
+    /// further down the chain (see `ValidatedParams`).
+    pub fn add_validator_ctx<F>(&mut self, param_name: ParameterType<'static>, validator: F)
+    where
+        F: for<'r> Fn(&str, &RequestInfo<'r>) -> Result<Sanitized, T> + Send + Sync + 'static,
     {
         let validator = Arc::new(validator);
         let validator_moved = Arc::clone(&validator);
@@ -145,6 +490,143 @@ where
             .and_modify(|e| e.push(validator_moved))
             .or_insert(vec![validator]);
     }
+
+    /// Explicit alias for `add_validator`: `QueryParam`, `Header` and
+    /// `Cookie` validators already run against every value bound to that
+    /// name, so this just names that intent at the call site.
+    pub fn add_validator_each<F>(&mut self, param_name: ParameterType<'static>, validator: F)
+    where
+        F: Fn(&str) -> Result<(), T> + Send + Sync + 'static,
+    {
+        self.add_validator(param_name, validator);
+    }
+
+    /// Asserts that a repeatable `QueryParam`/`Header`/`Cookie` was supplied
+    /// between `min` and `max` times (inclusive), independent of whether
+    /// each individual value is itself valid. `build_error` turns the
+    /// offending count into the middleware's error type.
+    pub fn add_validator_count<F>(
+        &mut self,
+        param_name: ParameterType<'static>,
+        min: usize,
+        max: usize,
+        build_error: F,
+    ) where
+        F: Fn(usize) -> T + Send + Sync + 'static,
+    {
+        self.count_validators
+            .insert(param_name, (min, max, Arc::new(build_error)));
+    }
+
+    /// Marks `param_name` as required: `handle` checks for its presence
+    /// before running any value validators and, if it's missing, builds an
+    /// error from `build_error` describing which field was absent.
+    pub fn add_required<F>(&mut self, param_name: ParameterType<'static>, build_error: F)
+    where
+        F: Fn(&ParameterType<'static>) -> T + Send + Sync + 'static,
+    {
+        self.required.insert(param_name, Arc::new(build_error));
+    }
+
+    /// Registers a typed validator: the located value is deserialized into
+    /// `D` with `serde` before `validator` ever sees it, replacing hand
+    /// written `is_number`/`is_bool`-style string parsers with, e.g.,
+    /// `validate_as::<i64, _, _>(Param("n"), on_type_mismatch, |n| ...)`.
+    /// `on_type_mismatch` builds the error for a value that doesn't
+    /// deserialize into `D` at all (the `ParameterType` it was registered
+    /// against, the raw value, and `D`'s type name are passed in, so the
+    /// error can name the field even under `fail_fast`); `validator` only
+    /// runs once that has succeeded, so it can focus purely on business
+    /// rules like range checks.
+    ///
+    /// Only scalar locations deserialize today: the located value always
+    /// goes through `stringify_json_scalar`/a plain `&str` before reaching
+    /// `D`, so a `BodyField` pointing at a JSON object or array is treated
+    /// as "not found" rather than handed to `D`'s deserializer as a subtree.
+    /// `validate_as::<SomeStruct>(BodyField("address"), ..)` won't run.
+    pub fn validate_as<D, F, E>(
+        &mut self,
+        param_name: ParameterType<'static>,
+        on_type_mismatch: E,
+        validator: F,
+    ) where
+        D: DeserializeOwned + 'static,
+        F: Fn(&D) -> Result<(), T> + Send + Sync + 'static,
+        E: Fn(&ParameterType<'static>, &str, &'static str) -> T + Send + Sync + 'static,
+    {
+        let expected_type = std::any::type_name::<D>();
+        let mismatch_param = param_name.clone();
+        self.add_validator_ctx(param_name, move |value, _info| {
+            // Numbers and booleans are read from the value as JSON; anything
+            // that isn't valid JSON for `D` falls back to the plain string.
+            let parsed = serde_json::from_str::<D>(value).or_else(|_| {
+                let deserializer: StrDeserializer<'_, ValueError> = value.into_deserializer();
+                D::deserialize(deserializer)
+            });
+            match parsed {
+                Ok(typed) => validator(&typed).map(|_| Sanitized::Unchanged),
+                Err(_) => Err(on_type_mismatch(&mismatch_param, value, expected_type)),
+            }
+        });
+    }
+
+    /// Registers a validator that needs to `.await` something (a database
+    /// lookup, a cache check, a remote call) instead of judging the value on
+    /// the spot. Async validators run after every sync validator for the
+    /// same `ParameterType` and only against values those already accepted.
+    pub fn add_async_validator<F, Fut>(&mut self, param_name: ParameterType<'static>, validator: F)
+    where
+        F: Fn(String) -> Fut + Send + Sync + 'static,
+        Fut: std::future::Future<Output = Result<(), T>> + Send + 'static,
+    {
+        let validator: Arc<
+            dyn Fn(String) -> BoxFuture<'static, Result<(), T>> + Send + Sync + 'static,
+        > = Arc::new(move |value: String| {
+            Box::pin(validator(value)) as BoxFuture<'static, Result<(), T>>
+        });
+        let validator_moved = Arc::clone(&validator);
+        self.async_validators
+            .entry(param_name)
+            .and_modify(|e| e.push(validator_moved))
+            .or_insert(vec![validator]);
+    }
+}
+
+/// Reads the whole body once, parsing it according to its `Content-Type`,
+/// then writes the bytes straight back onto `ctx` so `next.run(ctx)` still
+/// sees a full, unconsumed body.
+async fn load_body_data<State>(ctx: &mut Request<State>) -> io::Result<BodyData> {
+    let content_type = ctx.header("content-type").unwrap_or("").to_string();
+    let body_bytes = ctx.body_bytes().await?;
+    ctx.set_body(body_bytes.clone());
+
+    if body_bytes.is_empty() {
+        return Ok(BodyData::Absent);
+    }
+
+    // Content-Type token comparison is case-insensitive (RFC 7231); only use
+    // the lowercased form to pick a branch. `content_type` itself stays
+    // untouched since the multipart boundary *value* it carries is not.
+    let content_type_lower = content_type.to_lowercase();
+
+    if content_type_lower.starts_with("application/json") {
+        match serde_json::from_slice(&body_bytes) {
+            Ok(value) => Ok(BodyData::Json(value)),
+            Err(_) => Ok(BodyData::Invalid(
+                String::from_utf8_lossy(&body_bytes).into_owned(),
+            )),
+        }
+    } else if content_type_lower.starts_with("application/x-www-form-urlencoded") {
+        Ok(BodyData::Form(parse_urlencoded_form(&body_bytes)))
+    } else if content_type_lower.starts_with("multipart/form-data") {
+        let form = match extract_boundary(&content_type) {
+            Some(boundary) => parse_multipart_form(&body_bytes, boundary),
+            None => HashMap::new(),
+        };
+        Ok(BodyData::Form(form))
+    } else {
+        Ok(BodyData::Absent)
+    }
 }
 
 impl<State, T> Middleware<State> for ValidatorMiddleware<T>
@@ -154,96 +636,343 @@ where
 {
     fn handle<'a>(&'a self, ctx: Request<State>, next: Next<'a, State>) -> BoxFuture<'a, Response> {
         Box::pin(async move {
-            let mut query_parameters: Option<HashMap<String, String>> = None;
+            let mut ctx = ctx;
+            let query_parameters = parse_query_multimap(ctx.uri().query().unwrap_or(""));
+            let request_info = RequestInfo {
+                method: ctx.method().to_string(),
+                path: ctx.uri().path().to_string(),
+                peer_addr: ctx.peer_addr().map(|addr| addr.to_string()),
+                query: &query_parameters,
+            };
+            let mut cookie_parameters: Option<MultiMap<String, String>> = None;
+            let mut body_data: Option<BodyData> = None;
+            let mut errors: Vec<FieldError<T>> = Vec::new();
+            let mut sanitized: MultiMap<ParameterType<'static>, String> = MultiMap::new();
+            let mut failed_values: HashMap<
+                ParameterType<'static>,
+                std::collections::HashSet<String>,
+            > = HashMap::new();
+
+            macro_rules! fail_or_collect {
+                ($param_type:expr, $value:expr, $err:expr) => {
+                    if self.fail_fast {
+                        return Response::new(400).body_json(&$err).unwrap_or_else(|err| {
+                            Response::new(500).body_string(format!(
+                                "cannot serialize your parameter validator for '{}' error : {:?}",
+                                $param_type.name(),
+                                err
+                            ))
+                        });
+                    } else {
+                        errors.push(FieldError::new(&$param_type, $value, $err));
+                    }
+                };
+            }
+
+            // Runs `validator` against `value`, collecting its error like
+            // `fail_or_collect!` or, on a `Sanitized::Replace`, stashing the
+            // normalized value for `ValidatedParams`.
+            macro_rules! run_validator {
+                ($validator:expr, $value:expr, $param_type:expr) => {
+                    match $validator($value, &request_info) {
+                        Ok(Sanitized::Unchanged) => {}
+                        Ok(Sanitized::Replace(new_value)) => {
+                            sanitized.insert($param_type.clone(), new_value);
+                        }
+                        Err(err) => {
+                            failed_values
+                                .entry($param_type.clone())
+                                .or_insert_with(std::collections::HashSet::new)
+                                .insert($value.to_string());
+                            fail_or_collect!($param_type, $value, err);
+                        }
+                    }
+                };
+            }
+
+            for (param_name, build_error) in &self.required {
+                let present = match param_name {
+                    ParameterType::Param(name) => ctx.param::<String>(name).is_ok(),
+                    ParameterType::QueryParam(name) => query_parameters
+                        .get_vec(&name[..])
+                        .map_or(false, |values| !values.is_empty()),
+                    ParameterType::Header(name) => !header_all_values(&ctx, name).is_empty(),
+                    ParameterType::Cookie(name) => {
+                        if cookie_parameters.is_none() {
+                            cookie_parameters =
+                                Some(parse_cookie_multimap(ctx.header("cookie").unwrap_or("")));
+                        }
+                        cookie_parameters
+                            .as_ref()
+                            .unwrap()
+                            .get_vec(&name[..])
+                            .map_or(false, |values| !values.is_empty())
+                    }
+                    ParameterType::BodyField(path) => {
+                        if body_data.is_none() {
+                            match load_body_data(&mut ctx).await {
+                                Ok(data) => body_data = Some(data),
+                                Err(err) => {
+                                    return Response::new(500).body_string(format!(
+                                        "cannot read request body: {:?}",
+                                        err
+                                    ))
+                                }
+                            }
+                        }
+                        match body_data.as_ref().unwrap() {
+                            BodyData::Json(value) => lookup_json_path(value, path)
+                                .and_then(stringify_json_scalar)
+                                .is_some(),
+                            BodyData::Form(form) => {
+                                form.get(*path).map_or(false, |values| !values.is_empty())
+                            }
+                            BodyData::Invalid(_) => false,
+                            BodyData::Absent => false,
+                        }
+                    }
+                    ParameterType::Body => {
+                        if body_data.is_none() {
+                            match load_body_data(&mut ctx).await {
+                                Ok(data) => body_data = Some(data),
+                                Err(err) => {
+                                    return Response::new(500).body_string(format!(
+                                        "cannot read request body: {:?}",
+                                        err
+                                    ))
+                                }
+                            }
+                        }
+                        !matches!(body_data.as_ref().unwrap(), BodyData::Absent)
+                    }
+                };
+
+                if !present {
+                    let err = build_error(param_name);
+                    fail_or_collect!(param_name, String::new(), err);
+                }
+            }
 
             for (param_name, validators) in &self.validators {
                 match param_name {
-                    ParameterType::Param(param_name) => {
+                    ParameterType::Param(name) => {
                         for validator in validators {
-                            let param_found: Result<String, _> = ctx.param(param_name);
+                            let param_found: Result<String, _> = ctx.param(name);
                             if let Ok(param_value) = param_found {
-                                if let Err(err) = validator(&param_value[..]) {
-                                    return Response::new(400).body_json(&err).unwrap_or_else(
-                                        |err| {
-                                            return Response::new(500).body_string(format!(
-                                                "cannot serialize your parameter validator for '{}' error : {:?}",
-                                                param_name,
-                                                err
-                                            ));
-                                        },
-                                    );
+                                run_validator!(validator, &param_value[..], param_name);
+                            }
+                        }
+                    }
+                    ParameterType::QueryParam(name) => {
+                        if let Some(values) = query_parameters.get_vec(&name[..]) {
+                            for value in values {
+                                for validator in validators {
+                                    run_validator!(validator, &value[..], param_name);
                                 }
                             }
                         }
                     }
-                    ParameterType::QueryParam(param_name) => {
-                        if query_parameters.is_none() {
-                            match ctx.query::<HashMap<String, String>>() {
+                    ParameterType::Header(name) => {
+                        let values = header_all_values(&ctx, name);
+                        for value in &values {
+                            for validator in validators {
+                                run_validator!(validator, &value[..], param_name);
+                            }
+                        }
+                    }
+                    ParameterType::Cookie(name) => {
+                        if cookie_parameters.is_none() {
+                            cookie_parameters =
+                                Some(parse_cookie_multimap(ctx.header("cookie").unwrap_or("")));
+                        }
+                        let cookie_parameters = cookie_parameters.as_ref().unwrap();
+
+                        if let Some(values) = cookie_parameters.get_vec(&name[..]) {
+                            for value in values {
+                                for validator in validators {
+                                    run_validator!(validator, &value[..], param_name);
+                                }
+                            }
+                        }
+                    }
+                    ParameterType::BodyField(path) => {
+                        if body_data.is_none() {
+                            match load_body_data(&mut ctx).await {
+                                Ok(data) => body_data = Some(data),
                                 Err(err) => {
                                     return Response::new(500).body_string(format!(
-                                        "cannot read query parameters: {:?}",
+                                        "cannot read request body: {:?}",
                                         err
                                     ))
                                 }
-                                Ok(qps) => query_parameters = Some(qps),
                             }
                         }
-                        let query_parameters = query_parameters.as_ref().unwrap();
 
-                        if let Some(qp_value) = query_parameters.get(&param_name[..]) {
-                            for validator in validators {
-                                if let Err(err) = validator(qp_value) {
-                                    return Response::new(400).body_json(&err).unwrap_or_else(
-                                        |err| {
-                                            return Response::new(500).body_string(format!(
-                                                "cannot serialize your query parameter validator for '{}' error : {:?}",
-                                                param_name,
-                                                err
-                                            ));
-                                        },
-                                    );
+                        match body_data.as_ref().unwrap() {
+                            BodyData::Json(value) => {
+                                if let Some(found) = lookup_json_path(value, path) {
+                                    if let Some(found_value) = stringify_json_scalar(found) {
+                                        for validator in validators {
+                                            run_validator!(validator, &found_value[..], param_name);
+                                        }
+                                    }
                                 }
                             }
+                            BodyData::Form(form) => {
+                                if let Some(values) = form.get(*path) {
+                                    for value in values {
+                                        for validator in validators {
+                                            run_validator!(validator, &value[..], param_name);
+                                        }
+                                    }
+                                }
+                            }
+                            BodyData::Invalid(_) => {}
+                            BodyData::Absent => {}
                         }
                     }
-                    ParameterType::Header(header_name) => {
-                        for validator in validators {
-                            let header_found: Option<&str> = ctx.header(header_name);
-                            if let Some(header_value) = header_found {
-                                if let Err(err) = validator(header_value) {
-                                    return Response::new(400).body_json(&err).unwrap_or_else(
-                                        |err| {
-                                            return Response::new(500).body_string(format!(
-                                                "cannot serialize your header validator for '{}' error : {:?}",
-                                                header_name,
-                                                err
-                                            ));
-                                        },
-                                    );
+                    ParameterType::Body => {
+                        if body_data.is_none() {
+                            match load_body_data(&mut ctx).await {
+                                Ok(data) => body_data = Some(data),
+                                Err(err) => {
+                                    return Response::new(500).body_string(format!(
+                                        "cannot read request body: {:?}",
+                                        err
+                                    ))
                                 }
                             }
                         }
+
+                        let whole_body = match body_data.as_ref().unwrap() {
+                            BodyData::Json(value) => Some(value.to_string()),
+                            BodyData::Form(form) => Some(format!("{:?}", form)),
+                            BodyData::Invalid(raw) => Some(raw.clone()),
+                            BodyData::Absent => None,
+                        };
+                        if let Some(whole_body) = whole_body {
+                            for validator in validators {
+                                run_validator!(validator, &whole_body[..], param_name);
+                            }
+                        }
                     }
-                    ParameterType::Cookie(cookie_name) => {
-                        for validator in validators {
-                            let cookie_found = ctx.cookie(cookie_name);
-                            if let Some(cookie) = cookie_found {
-                                if let Err(err) = validator(cookie.value()) {
-                                    return Response::new(400).body_json(&err).unwrap_or_else(
-                                        |err| {
-                                            return Response::new(500).body_string(format!(
-                                                "cannot serialize your cookie validator for '{}' error : {:?}",
-                                                cookie_name,
-                                                err
-                                            ));
-                                        },
-                                    );
+                }
+            }
+
+            for (param_name, (min, max, build_error)) in &self.count_validators {
+                let count = match param_name {
+                    ParameterType::QueryParam(name) => query_parameters
+                        .get_vec(&name[..])
+                        .map(|values| values.len())
+                        .unwrap_or(0),
+                    ParameterType::Header(name) => header_all_values(&ctx, name).len(),
+                    ParameterType::Cookie(name) => {
+                        if cookie_parameters.is_none() {
+                            cookie_parameters =
+                                Some(parse_cookie_multimap(ctx.header("cookie").unwrap_or("")));
+                        }
+                        cookie_parameters
+                            .as_ref()
+                            .unwrap()
+                            .get_vec(&name[..])
+                            .map(|values| values.len())
+                            .unwrap_or(0)
+                    }
+                    _ => continue,
+                };
+
+                if count < *min || count > *max {
+                    let err = build_error(count);
+                    fail_or_collect!(param_name, count.to_string(), err);
+                }
+            }
+
+            for (param_name, validators) in &self.async_validators {
+                let values: Vec<String> = match param_name {
+                    ParameterType::Param(name) => ctx.param::<String>(name).into_iter().collect(),
+                    ParameterType::QueryParam(name) => query_parameters
+                        .get_vec(&name[..])
+                        .cloned()
+                        .unwrap_or_default(),
+                    ParameterType::Header(name) => header_all_values(&ctx, name),
+                    ParameterType::Cookie(name) => {
+                        if cookie_parameters.is_none() {
+                            cookie_parameters =
+                                Some(parse_cookie_multimap(ctx.header("cookie").unwrap_or("")));
+                        }
+                        cookie_parameters
+                            .as_ref()
+                            .unwrap()
+                            .get_vec(&name[..])
+                            .cloned()
+                            .unwrap_or_default()
+                    }
+                    ParameterType::BodyField(path) => {
+                        if body_data.is_none() {
+                            match load_body_data(&mut ctx).await {
+                                Ok(data) => body_data = Some(data),
+                                Err(err) => {
+                                    return Response::new(500).body_string(format!(
+                                        "cannot read request body: {:?}",
+                                        err
+                                    ))
+                                }
+                            }
+                        }
+                        match body_data.as_ref().unwrap() {
+                            BodyData::Json(value) => lookup_json_path(value, path)
+                                .and_then(stringify_json_scalar)
+                                .into_iter()
+                                .collect(),
+                            BodyData::Form(form) => form.get(*path).cloned().unwrap_or_default(),
+                            BodyData::Invalid(_) => Vec::new(),
+                            BodyData::Absent => Vec::new(),
+                        }
+                    }
+                    ParameterType::Body => {
+                        if body_data.is_none() {
+                            match load_body_data(&mut ctx).await {
+                                Ok(data) => body_data = Some(data),
+                                Err(err) => {
+                                    return Response::new(500).body_string(format!(
+                                        "cannot read request body: {:?}",
+                                        err
+                                    ))
                                 }
                             }
                         }
+                        match body_data.as_ref().unwrap() {
+                            BodyData::Json(value) => vec![value.to_string()],
+                            BodyData::Form(form) => vec![format!("{:?}", form)],
+                            BodyData::Invalid(raw) => vec![raw.clone()],
+                            BodyData::Absent => Vec::new(),
+                        }
+                    }
+                };
+
+                let already_failed = failed_values.get(param_name);
+                for value in &values {
+                    if already_failed.map_or(false, |bad| bad.contains(value)) {
+                        continue;
+                    }
+                    for validator in validators {
+                        if let Err(err) = validator(value.clone()).await {
+                            fail_or_collect!(param_name, value.clone(), err);
+                        }
                     }
                 }
             }
+
+            if !errors.is_empty() {
+                return Response::new(422).body_json(&errors).unwrap_or_else(|err| {
+                    Response::new(500).body_string(format!(
+                        "cannot serialize aggregated validation errors : {:?}",
+                        err
+                    ))
+                });
+            }
+
+            ctx.set_ext(ValidatedParams(sanitized));
             next.run(ctx).await
         })
     }